@@ -0,0 +1,26 @@
+// Copyright (c) 2017-2026, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+include!("build/rustc_version.rs");
+include!("build/target_features.rs");
+
+fn main() {
+  built::write_built_file().expect("Failed to acquire build-time information");
+
+  emit_rustc_cfg_check_cfg();
+  emit_feature_matrix_check_cfg();
+
+  if let Some(raw) = rustc_version_string() {
+    emit_rustc_cfg_gates(&raw);
+  }
+  emit_feature_matrix_cfg_gates();
+
+  println!("cargo:rerun-if-env-changed=RUSTC");
+  println!("cargo:rerun-if-env-changed=CARGO_FEATURE_THREADING");
+}
@@ -0,0 +1,160 @@
+// Copyright (c) 2017-2026, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! Parsing of the `rustc -V` banner into a structured, comparable form.
+//!
+//! This lives alongside `build.rs` (via `include!`) rather than under
+//! `src/`, since it only ever runs at build time: the parsed version feeds
+//! `cargo:rustc-cfg` gates, not anything shipped in the compiled crate.
+
+use std::cmp::Ordering;
+
+/// A parsed `rustc -V` banner, e.g. `rustc 1.93.0 (254b59607 2026-01-19)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RustcVersion {
+  major: u32,
+  minor: u32,
+  patch: u32,
+  channel: Channel,
+  /// The `YYYY-MM-DD` commit date, if the banner had one.
+  commit_date: Option<(u32, u32, u32)>,
+}
+
+/// Ordered so that, for equal `major.minor.patch`, a nightly always
+/// compares below the stable release of the same number: a nightly is a
+/// preview of that release, not proof the release has shipped yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Channel {
+  Nightly,
+  Beta,
+  Stable,
+}
+
+/// A named `cfg` gate and the minimum *stable* `rustc` it requires.
+struct FeatureThreshold {
+  cfg: &'static str,
+  min: (u32, u32, u32),
+}
+
+/// Compiler versions that unlock newer SIMD/codegen paths (stabilized
+/// `std::arch` AVX-512 and AArch64 SVE intrinsics, etc.). Add an entry
+/// here and gate the corresponding code path on `cfg(<cfg>)`.
+const FEATURE_THRESHOLDS: &[FeatureThreshold] = &[
+  FeatureThreshold { cfg: "rav1e_rustc_ge_1_89", min: (1, 89, 0) },
+  FeatureThreshold { cfg: "rav1e_rustc_ge_1_91", min: (1, 91, 0) },
+];
+
+impl RustcVersion {
+  /// Parses the output of `rustc -V`, e.g.
+  /// `"rustc 1.93.0 (254b59607 2026-01-19)"` or
+  /// `"rustc 1.94.0-nightly (abcdef123 2026-02-01)"`.
+  ///
+  /// Returns `None` for anything that doesn't look like the expected
+  /// banner (custom toolchains, vendored rustc forks, etc.) so the
+  /// caller can fall back to gating every new path off.
+  fn parse(raw: &str) -> Option<Self> {
+    let rest = raw.trim().strip_prefix("rustc ")?;
+    let (version_token, tail) = rest.split_once(' ').unwrap_or((rest, ""));
+
+    let channel = if version_token.contains("-nightly") {
+      Channel::Nightly
+    } else if version_token.contains("-beta") {
+      Channel::Beta
+    } else {
+      Channel::Stable
+    };
+
+    let numeric = version_token.split('-').next().unwrap_or(version_token);
+    let mut parts = numeric.splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next().unwrap_or("0").parse().ok()?;
+
+    let commit_date = tail
+      .trim_start_matches('(')
+      .trim_end_matches(')')
+      .rsplit(' ')
+      .next()
+      .and_then(parse_commit_date);
+
+    Some(RustcVersion { major, minor, patch, channel, commit_date })
+  }
+
+  /// `true` if this version is new enough to satisfy a stable-release
+  /// threshold of `min`.
+  fn satisfies(&self, min: (u32, u32, u32)) -> bool {
+    let threshold =
+      RustcVersion { major: min.0, minor: min.1, patch: min.2, channel: Channel::Stable, commit_date: None };
+    self.cmp(&threshold) != Ordering::Less
+  }
+}
+
+impl PartialOrd for RustcVersion {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for RustcVersion {
+  fn cmp(&self, other: &Self) -> Ordering {
+    (self.major, self.minor, self.patch)
+      .cmp(&(other.major, other.minor, other.patch))
+      .then_with(|| self.channel.cmp(&other.channel))
+      .then_with(|| match (self.channel, other.channel) {
+        (Channel::Nightly, Channel::Nightly) => self.commit_date.cmp(&other.commit_date),
+        _ => Ordering::Equal,
+      })
+  }
+}
+
+fn parse_commit_date(s: &str) -> Option<(u32, u32, u32)> {
+  let mut parts = s.splitn(3, '-');
+  let y = parts.next()?.parse().ok()?;
+  let m = parts.next()?.parse().ok()?;
+  let d = parts.next()?.parse().ok()?;
+  Some((y, m, d))
+}
+
+/// Runs `-V` on the compiler cargo resolved for this build (`$RUSTC`,
+/// falling back to `rustc` on `$PATH`), mirroring what `built` captures
+/// into `RUSTC_VERSION`.
+fn rustc_version_string() -> Option<String> {
+  let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+  let output = std::process::Command::new(rustc).arg("-V").output().ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  String::from_utf8(output.stdout).ok().map(|s| s.trim().to_owned())
+}
+
+/// Parses `raw` and emits a `cargo:rustc-cfg` line for every feature
+/// threshold it satisfies. An unparseable banner (custom toolchain, rustc
+/// fork) is treated as "assume the minimum supported compiler": nothing
+/// is emitted, so the newer intrinsic paths stay off and the existing
+/// SIMD routines are used instead.
+fn emit_rustc_cfg_gates(raw: &str) {
+  let Some(version) = RustcVersion::parse(raw) else {
+    return;
+  };
+  for threshold in FEATURE_THRESHOLDS {
+    if version.satisfies(threshold.min) {
+      println!("cargo:rustc-cfg={}", threshold.cfg);
+    }
+  }
+}
+
+/// Declares every `cfg` name [`emit_rustc_cfg_gates`] can set, so `rustc`'s
+/// `unexpected_cfgs` lint doesn't fire at the `cfg(rav1e_rustc_ge_*)` use
+/// sites when the corresponding threshold isn't satisfied. Must run
+/// unconditionally, independent of whether the version banner parses.
+fn emit_rustc_cfg_check_cfg() {
+  for threshold in FEATURE_THRESHOLDS {
+    println!("cargo::rustc-check-cfg=cfg({})", threshold.cfg);
+  }
+}
@@ -0,0 +1,93 @@
+// Copyright (c) 2017-2026, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! Derives a valid per-target feature matrix from `CARGO_CFG_TARGET_ARCH`
+//! and `CARGO_CFG_TARGET_OS`, so a `no_std`-
+//! capable encode core can be cross-compiled for bare-metal ARM (e.g.
+//! `thumbv6m-none-eabi`, `thumbv7m-none-eabi`) without hand-editing Cargo
+//! features.
+//!
+//! Incompatible feature/target combinations are rejected here, at
+//! configure time, rather than left to fail deep in codegen or linking.
+
+use std::env;
+
+/// The SIMD backend selected for this target's intrinsics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SimdBackend {
+  X86_64,
+  Aarch64,
+  Scalar,
+}
+
+impl SimdBackend {
+  fn select(target_arch: &str) -> Self {
+    match target_arch {
+      "x86_64" => SimdBackend::X86_64,
+      "aarch64" => SimdBackend::Aarch64,
+      _ => SimdBackend::Scalar,
+    }
+  }
+
+  fn cfg_name(self) -> &'static str {
+    match self {
+      SimdBackend::X86_64 => "rav1e_simd_x86_64",
+      SimdBackend::Aarch64 => "rav1e_simd_aarch64",
+      SimdBackend::Scalar => "rav1e_simd_scalar",
+    }
+  }
+}
+
+/// `true` for the bare-metal `*-none-*` targets used by the embedded
+/// cross toolchains, which have no OS to provide threads or an allocator.
+fn is_bare_metal(target_os: &str) -> bool {
+  target_os == "none"
+}
+
+/// Reads the target cfg cargo sets for build scripts, derives the
+/// feature matrix for this target, and emits the resulting
+/// `cargo:rustc-cfg` flags.
+///
+/// Panics (failing the build script) if the requested Cargo features
+/// can't work on this target, e.g. `threading` on a bare-metal target
+/// with no OS thread support.
+fn emit_feature_matrix_cfg_gates() {
+  let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+  let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+  let bare_metal = is_bare_metal(&target_os);
+
+  let threading_requested = env::var_os("CARGO_FEATURE_THREADING").is_some();
+  if bare_metal && threading_requested {
+    panic!(
+      "the `threading` feature requires OS thread support and cannot be \
+       built for bare-metal target arch `{target_arch}` (target_os = \
+       \"none\"); disable `--features threading` for this target"
+    );
+  }
+
+  if bare_metal {
+    println!("cargo:rustc-cfg=rav1e_no_std");
+  } else {
+    println!("cargo:rustc-cfg=rav1e_threading_capable");
+  }
+
+  println!("cargo:rustc-cfg={}", SimdBackend::select(&target_arch).cfg_name());
+}
+
+/// Declares every `cfg` name [`emit_feature_matrix_cfg_gates`] can set, so
+/// `rustc`'s `unexpected_cfgs` lint doesn't fire at the `cfg(rav1e_no_std)`
+/// / `cfg(rav1e_simd_*)` use sites for targets that don't set them. Must
+/// run unconditionally, independent of which target is being built.
+fn emit_feature_matrix_check_cfg() {
+  println!("cargo::rustc-check-cfg=cfg(rav1e_no_std)");
+  println!("cargo::rustc-check-cfg=cfg(rav1e_threading_capable)");
+  println!("cargo::rustc-check-cfg=cfg(rav1e_simd_x86_64)");
+  println!("cargo::rustc-check-cfg=cfg(rav1e_simd_aarch64)");
+  println!("cargo::rustc-check-cfg=cfg(rav1e_simd_scalar)");
+}
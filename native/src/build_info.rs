@@ -0,0 +1,73 @@
+// Copyright (c) 2017-2026, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! Runtime access to the build-time metadata captured by `build.rs`.
+//!
+//! The raw constants (`PKG_VERSION`, `TARGET`, `FEATURES`, ...) are
+//! generated by the `built` crate into `$OUT_DIR/built.rs`. This module
+//! wraps them in a stable, typed API so embedders don't have to parse the
+//! raw strings themselves, and so the values cross the C ABI via
+//! [`crate::capi`].
+
+mod built_info {
+  // Generated at build time; see `build.rs`.
+  include!(concat!(env!("OUT_DIR"), "/built.rs"));
+}
+
+/// A parsed `major.minor.patch` version, as reported by Cargo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Version {
+  pub major: u32,
+  pub minor: u32,
+  pub patch: u32,
+}
+
+impl Version {
+  fn parse(s: &str) -> Option<Self> {
+    let mut parts = s.splitn(3, '.');
+    Some(Version {
+      major: parts.next()?.parse().ok()?,
+      minor: parts.next()?.parse().ok()?,
+      patch: parts.next().unwrap_or("0").parse().ok()?,
+    })
+  }
+}
+
+/// Build-time metadata describing the rav1e binary that produced (or will
+/// produce) a bitstream.
+///
+/// Embedders should log this alongside any reproducibility-sensitive
+/// artifact (crash reports, conformance failures): rav1e ships across many
+/// target triples and feature combinations, and two builds that look
+/// identical on paper can behave differently.
+#[derive(Debug, Clone)]
+pub struct BuildInfo {
+  /// The encoder's own version, e.g. `0.8.1`.
+  pub version: Version,
+  /// The target triple the encoder was compiled for.
+  pub target_triple: &'static str,
+  /// `release` or `debug`.
+  pub profile: &'static str,
+  /// The compile-time Cargo features that were enabled, lowercased.
+  pub features: &'static [&'static str],
+  /// The `rustc -V` banner of the compiler that built this binary.
+  pub rustc_version: &'static str,
+}
+
+/// Returns metadata describing how this copy of rav1e was built.
+pub fn build_info() -> BuildInfo {
+  BuildInfo {
+    version: Version::parse(built_info::PKG_VERSION)
+      .unwrap_or(Version { major: 0, minor: 0, patch: 0 }),
+    target_triple: built_info::TARGET,
+    profile: built_info::PROFILE,
+    features: &built_info::FEATURES_LOWERCASE,
+    rustc_version: built_info::RUSTC_VERSION,
+  }
+}
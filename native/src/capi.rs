@@ -0,0 +1,87 @@
+// Copyright (c) 2017-2026, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! C-compatible bindings, built when the `capi` feature is enabled.
+//!
+//! See `include/rav1e/rav1e.h` for the corresponding C declarations.
+
+use crate::build_info;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::sync::OnceLock;
+
+/// C-compatible mirror of [`build_info::BuildInfo`].
+///
+/// The string fields are owned, NUL-terminated, and must be released with
+/// [`rav1e_build_info_unref`].
+#[repr(C)]
+pub struct RaBuildInfo {
+  pub major: u32,
+  pub minor: u32,
+  pub patch: u32,
+  pub target_triple: *mut c_char,
+  pub profile: *mut c_char,
+  pub rustc_version: *mut c_char,
+}
+
+fn to_c_string(s: &str) -> *mut c_char {
+  CString::new(s).unwrap_or_default().into_raw()
+}
+
+/// Returns a heap-allocated snapshot of this build's metadata.
+///
+/// Must be freed with [`rav1e_build_info_unref`].
+#[no_mangle]
+pub extern fn rav1e_build_info() -> *mut RaBuildInfo {
+  let info = build_info::build_info();
+  Box::into_raw(Box::new(RaBuildInfo {
+    major: info.version.major,
+    minor: info.version.minor,
+    patch: info.version.patch,
+    target_triple: to_c_string(info.target_triple),
+    profile: to_c_string(info.profile),
+    rustc_version: to_c_string(info.rustc_version),
+  }))
+}
+
+/// Frees a [`RaBuildInfo`] returned by [`rav1e_build_info`].
+///
+/// # Safety
+/// `info` must either be null or a pointer previously returned by
+/// [`rav1e_build_info`] that has not already been freed.
+#[no_mangle]
+pub unsafe extern fn rav1e_build_info_unref(info: *mut RaBuildInfo) {
+  if info.is_null() {
+    return;
+  }
+  let info = Box::from_raw(info);
+  drop(CString::from_raw(info.target_triple));
+  drop(CString::from_raw(info.profile));
+  drop(CString::from_raw(info.rustc_version));
+}
+
+/// Returns the full encoder version plus the compiler that produced this
+/// build, e.g. `"0.8.1 (rustc 1.93.0 (254b59607 2026-01-19))"`.
+///
+/// The returned pointer is valid for the lifetime of the program and must
+/// not be freed.
+#[no_mangle]
+pub extern fn rav1e_version_full() -> *const c_char {
+  static VERSION_FULL: OnceLock<CString> = OnceLock::new();
+  VERSION_FULL
+    .get_or_init(|| {
+      let info = build_info::build_info();
+      CString::new(format!(
+        "{}.{}.{} ({})",
+        info.version.major, info.version.minor, info.version.patch, info.rustc_version
+      ))
+      .unwrap_or_default()
+    })
+    .as_ptr()
+}
@@ -0,0 +1,226 @@
+// Copyright (c) 2017-2026, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! Runtime CPU capability detection, reconciled against the SIMD backend
+//! that `build.rs` actually compiled in (the `rav1e_simd_*` cfg gates from
+//! the per-target feature matrix).
+//!
+//! [`crate::build_info`] describes what this binary *can* do; this module
+//! describes what it *will* do on the machine it ends up running on.
+
+use std::cmp::min;
+use std::env;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The concrete SIMD instruction-set tier the encoder will dispatch to.
+///
+/// Variants are declared narrowest-to-widest so derived [`Ord`] matches
+/// dispatch precedence: a wider tier is always preferred when available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg(target_arch = "x86_64")]
+pub enum CpuFeatureLevel {
+  Scalar,
+  Sse4_1,
+  Avx2,
+  Avx512,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg(target_arch = "aarch64")]
+pub enum CpuFeatureLevel {
+  Scalar,
+  Neon,
+  Dotprod,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+pub enum CpuFeatureLevel {
+  Scalar,
+}
+
+impl CpuFeatureLevel {
+  /// The widest tier this build's SIMD backend could possibly dispatch
+  /// to, ignoring what the running CPU actually supports.
+  #[cfg(target_arch = "x86_64")]
+  const MAX: Self = CpuFeatureLevel::Avx512;
+  #[cfg(target_arch = "aarch64")]
+  const MAX: Self = CpuFeatureLevel::Dotprod;
+  #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+  const MAX: Self = CpuFeatureLevel::Scalar;
+
+  /// Parses a tier name as accepted by `RAV1E_CPU_FEATURE_LEVEL_OVERRIDE`
+  /// (case-insensitive), e.g. `"avx2"`, `"sse4.1"`, `"neon"`, `"scalar"`.
+  fn from_name(name: &str) -> Option<Self> {
+    #[cfg(target_arch = "x86_64")]
+    {
+      match name.to_ascii_lowercase().as_str() {
+        "scalar" => Some(CpuFeatureLevel::Scalar),
+        "sse4.1" | "sse4_1" => Some(CpuFeatureLevel::Sse4_1),
+        "avx2" => Some(CpuFeatureLevel::Avx2),
+        "avx512" => Some(CpuFeatureLevel::Avx512),
+        _ => None,
+      }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+      match name.to_ascii_lowercase().as_str() {
+        "scalar" => Some(CpuFeatureLevel::Scalar),
+        "neon" => Some(CpuFeatureLevel::Neon),
+        "dotprod" => Some(CpuFeatureLevel::Dotprod),
+        _ => None,
+      }
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+      match name.to_ascii_lowercase().as_str() {
+        "scalar" => Some(CpuFeatureLevel::Scalar),
+        _ => None,
+      }
+    }
+  }
+}
+
+/// The widest tier compiled into this binary by the SIMD backend `build.rs`
+/// selected for the target (see the `rav1e_simd_*` cfg gates).
+///
+/// A build without this arch's SIMD gate (e.g. one built with
+/// `rav1e_simd_scalar`, or with no `rav1e_simd_*` gate at all) is treated
+/// as scalar-only.
+fn compiled_in_max() -> CpuFeatureLevel {
+  #[cfg(target_arch = "x86_64")]
+  {
+    if cfg!(rav1e_simd_x86_64) {
+      CpuFeatureLevel::MAX
+    } else {
+      CpuFeatureLevel::Scalar
+    }
+  }
+  #[cfg(target_arch = "aarch64")]
+  {
+    if cfg!(rav1e_simd_aarch64) {
+      CpuFeatureLevel::MAX
+    } else {
+      CpuFeatureLevel::Scalar
+    }
+  }
+  #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+  {
+    CpuFeatureLevel::Scalar
+  }
+}
+
+/// Probes the running CPU for the widest tier it actually supports.
+#[cfg(target_arch = "x86_64")]
+fn detect_runtime_max() -> CpuFeatureLevel {
+  if std::is_x86_feature_detected!("avx512f") {
+    CpuFeatureLevel::Avx512
+  } else if std::is_x86_feature_detected!("avx2") {
+    CpuFeatureLevel::Avx2
+  } else if std::is_x86_feature_detected!("sse4.1") {
+    CpuFeatureLevel::Sse4_1
+  } else {
+    CpuFeatureLevel::Scalar
+  }
+}
+
+#[cfg(target_arch = "aarch64")]
+fn detect_runtime_max() -> CpuFeatureLevel {
+  if std::arch::is_aarch64_feature_detected!("dotprod") {
+    CpuFeatureLevel::Dotprod
+  } else if std::arch::is_aarch64_feature_detected!("neon") {
+    CpuFeatureLevel::Neon
+  } else {
+    CpuFeatureLevel::Scalar
+  }
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn detect_runtime_max() -> CpuFeatureLevel {
+  CpuFeatureLevel::Scalar
+}
+
+/// `u8::MAX` means "no override"; any other stored value is a valid
+/// `CpuFeatureLevel` discriminant.
+static LEVEL_OVERRIDE: AtomicU8 = AtomicU8::new(u8::MAX);
+
+const NO_OVERRIDE: u8 = u8::MAX;
+
+/// Forces [`cpu_features`] to report at most `level`, regardless of what
+/// the CPU and build actually support. It can only narrow the result,
+/// never widen it past what's actually available.
+///
+/// Intended for benchmarking and bug isolation, e.g. confirming a
+/// regression is specific to the AVX2 path. Pass `None` to clear the
+/// override.
+pub fn set_cpu_feature_level_override(level: Option<CpuFeatureLevel>) {
+  let encoded = level.map_or(NO_OVERRIDE, |l| l as u8);
+  LEVEL_OVERRIDE.store(encoded, Ordering::Relaxed);
+}
+
+/// Inverse of the `as u8` cast used by [`set_cpu_feature_level_override`].
+fn decode_override(stored: u8) -> Option<CpuFeatureLevel> {
+  #[cfg(target_arch = "x86_64")]
+  {
+    match stored {
+      0 => Some(CpuFeatureLevel::Scalar),
+      1 => Some(CpuFeatureLevel::Sse4_1),
+      2 => Some(CpuFeatureLevel::Avx2),
+      3 => Some(CpuFeatureLevel::Avx512),
+      _ => None,
+    }
+  }
+  #[cfg(target_arch = "aarch64")]
+  {
+    match stored {
+      0 => Some(CpuFeatureLevel::Scalar),
+      1 => Some(CpuFeatureLevel::Neon),
+      2 => Some(CpuFeatureLevel::Dotprod),
+      _ => None,
+    }
+  }
+  #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+  {
+    match stored {
+      0 => Some(CpuFeatureLevel::Scalar),
+      _ => None,
+    }
+  }
+}
+
+fn override_level() -> Option<CpuFeatureLevel> {
+  let stored = LEVEL_OVERRIDE.load(Ordering::Relaxed);
+  if stored != NO_OVERRIDE {
+    return decode_override(stored);
+  }
+  env::var("RAV1E_CPU_FEATURE_LEVEL_OVERRIDE").ok().and_then(|s| CpuFeatureLevel::from_name(&s))
+}
+
+/// The SIMD path the encoder will actually take on this machine: the
+/// intersection of what was compiled in and what the running CPU
+/// supports, optionally narrowed further by an override.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuFeatures {
+  pub level: CpuFeatureLevel,
+}
+
+/// Returns the concrete SIMD tier that will be dispatched, reconciling the
+/// compile-time SIMD backend (the `rav1e_simd_*` cfg gates set by
+/// `build.rs`) with what this CPU actually supports at runtime.
+///
+/// Can be forced down via [`set_cpu_feature_level_override`] or the
+/// `RAV1E_CPU_FEATURE_LEVEL_OVERRIDE` environment variable.
+pub fn cpu_features() -> CpuFeatures {
+  let available = min(detect_runtime_max(), compiled_in_max());
+  let level = match override_level() {
+    Some(forced) => min(available, forced),
+    None => available,
+  };
+  CpuFeatures { level }
+}
@@ -0,0 +1,18 @@
+// Copyright (c) 2017-2026, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! rav1e is an [AV1] video encoder written in Rust.
+//!
+//! [AV1]: https://aomediacodec.github.io/av1-spec/
+
+pub mod build_info;
+pub mod cpu_features;
+
+#[cfg(feature = "capi")]
+pub mod capi;